@@ -1,19 +1,91 @@
 use std::fs::OpenOptions;
-use std::io::{self, Write};
-use std::path::PathBuf;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
 use std::time::Duration;
 
 use chrono::{Datelike, Local};
 use chrono::Timelike;
-use clap::{Parser, Subcommand};
-use serialport::{available_ports, SerialPortType, UsbPortInfo};
+use clap::{Parser, Subcommand, ValueEnum};
+use configparser::ini::Ini;
+use regex::Regex;
+use serialport::{available_ports, SerialPort, SerialPortType, UsbPortInfo};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum LineEnding {
+    /// Append `\n`
+    Lf,
+    /// Append `\r\n`
+    CrLf,
+    /// Append nothing
+    None,
+}
+
+impl LineEnding {
+    fn as_bytes(self) -> &'static [u8] {
+        match self {
+            LineEnding::Lf => b"\n",
+            LineEnding::CrLf => b"\r\n",
+            LineEnding::None => b"",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, ValueEnum)]
+enum Format {
+    /// Log newline-delimited text as-is
+    #[default]
+    Text,
+    /// Log each chunk as space-separated `0x..` bytes
+    Hex,
+    /// Log each chunk as an offset + hex + ASCII gutter, like `hexdump -C`
+    Hexdump,
+}
+
+/// Options shared by every reading loop, bundled so they're easy to thread
+/// through `run_reader`/`run_duplex` and their background threads.
+#[derive(Debug, Clone)]
+struct ReadOptions {
+    output: Option<PathBuf>,
+    format: Format,
+    /// For `--format hex`/`hexdump`, the number of bytes to accumulate
+    /// before logging a chunk, if a read timeout doesn't flush it first.
+    chunk_size: usize,
+    /// Only log lines matching this regex (text format only)
+    filter: Option<Regex>,
+    /// Drop lines matching this regex (text format only)
+    exclude: Option<Regex>,
+    /// With `filter`, keep non-matching lines but ANSI-highlight the matched
+    /// span instead of dropping them
+    highlight: bool,
+    /// Byte that terminates a line (text format only)
+    delimiter: u8,
+    /// Max bytes a line (text format) can grow to without hitting `delimiter`
+    /// before it's flushed anyway
+    max_line_length: usize,
+}
 
 #[derive(Debug, Subcommand, Clone)]
 enum Command {
     Read {
-        /// The device path to a serial port
+        /// The device path to a serial port. Required unless --vid, --pid,
+        /// or --match is used to look it up instead.
         #[arg(short, long)]
-        port: String,
+        port: Option<String>,
+
+        /// Match a USB vendor ID (hex, e.g. 04d8) against `available_ports()`
+        #[arg(long, value_parser = parse_hex_u16)]
+        vid: Option<u16>,
+
+        /// Match a USB product ID (hex, e.g. 000a) against `available_ports()`
+        #[arg(long, value_parser = parse_hex_u16)]
+        pid: Option<u16>,
+
+        /// Match ports whose serial number, manufacturer, or product
+        /// contains this text
+        #[arg(long, value_name = "TEXT")]
+        r#match: Option<String>,
 
         /// Output file.
         #[arg(short, long, value_name = "FILE")]
@@ -22,9 +94,323 @@ enum Command {
         /// The baud rate to connect at
         #[arg(short, long)]
         baud: u32,
+
+        /// How to log received data
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// For --format hex/hexdump, bytes to accumulate before logging a
+        /// chunk if a read timeout doesn't flush it first
+        #[arg(long, value_parser = parse_chunk_size, default_value_t = 256)]
+        chunk_size: usize,
+
+        /// On disconnect (or initial open failure), keep retrying to open
+        /// the port with exponential backoff instead of exiting
+        #[arg(long)]
+        reconnect: bool,
+
+        /// Open an interactive session: keep logging incoming data while
+        /// sending lines typed on stdin to the port
+        #[arg(long)]
+        duplex: bool,
+
+        /// Line ending appended to each line sent to the port in duplex mode
+        #[arg(long, value_enum, default_value_t = LineEnding::Lf)]
+        line_ending: LineEnding,
+
+        /// Only log lines matching this regex
+        #[arg(long, value_name = "REGEX")]
+        filter: Option<String>,
+
+        /// Drop lines matching this regex
+        #[arg(long, value_name = "REGEX")]
+        exclude: Option<String>,
+
+        /// With --filter, keep non-matching lines but ANSI-highlight the
+        /// matched span instead of dropping them
+        #[arg(long)]
+        highlight: bool,
+
+        /// Byte that terminates a line: a single ASCII character, \n, \r,
+        /// \0, \t, or a 0xNN hex byte
+        #[arg(long, value_parser = parse_delimiter, default_value = "\\n")]
+        delimiter: u8,
+
+        /// Max bytes a line can grow to without hitting --delimiter before
+        /// it's logged anyway
+        #[arg(long, value_parser = parse_max_line_length, default_value_t = 64 * 1024)]
+        max_line_length: usize,
+    },
+
+    List {
+        /// Only list ports matching a USB vendor ID (hex, e.g. 04d8)
+        #[arg(long, value_parser = parse_hex_u16)]
+        vid: Option<u16>,
+
+        /// Only list ports matching a USB product ID (hex, e.g. 000a)
+        #[arg(long, value_parser = parse_hex_u16)]
+        pid: Option<u16>,
+
+        /// Only list ports whose serial number, manufacturer, or product
+        /// contains this text
+        #[arg(long, value_name = "TEXT")]
+        r#match: Option<String>,
     },
 
-    List,
+    /// Open a port using a named profile from a config file
+    Connect {
+        /// Name of the `[profile]` section to open
+        profile: String,
+
+        /// Path to the INI config file. Defaults to `slog.ini` in the
+        /// current directory, then `$HOME/.config/slog/slog.ini`.
+        #[arg(long, value_name = "FILE")]
+        config: Option<PathBuf>,
+
+        /// Overrides the profile's `port`
+        #[arg(short, long)]
+        port: Option<String>,
+
+        /// Overrides the profile's `vid`
+        #[arg(long, value_parser = parse_hex_u16)]
+        vid: Option<u16>,
+
+        /// Overrides the profile's `pid`
+        #[arg(long, value_parser = parse_hex_u16)]
+        pid: Option<u16>,
+
+        /// Overrides the profile's `match`
+        #[arg(long, value_name = "TEXT")]
+        r#match: Option<String>,
+
+        /// Overrides the profile's `output`
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+
+        /// Overrides the profile's `baud`
+        #[arg(short, long)]
+        baud: Option<u32>,
+
+        /// Overrides the profile's `format`
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+
+        /// Overrides the profile's `filter`
+        #[arg(long, value_name = "REGEX")]
+        filter: Option<String>,
+
+        /// Overrides the profile's `exclude`
+        #[arg(long, value_name = "REGEX")]
+        exclude: Option<String>,
+
+        /// Overrides the profile's `line_ending`
+        #[arg(long, value_enum)]
+        line_ending: Option<LineEnding>,
+
+        /// On disconnect (or initial open failure), keep retrying to open
+        /// the port with exponential backoff instead of exiting
+        #[arg(long)]
+        reconnect: bool,
+
+        /// Open an interactive session: keep logging incoming data while
+        /// sending lines typed on stdin to the port
+        #[arg(long)]
+        duplex: bool,
+
+        /// With --filter, keep non-matching lines but ANSI-highlight the
+        /// matched span instead of dropping them
+        #[arg(long)]
+        highlight: bool,
+
+        /// Byte that terminates a line: a single ASCII character, \n, \r,
+        /// \0, \t, or a 0xNN hex byte
+        #[arg(long, value_parser = parse_delimiter, default_value = "\\n")]
+        delimiter: u8,
+
+        /// Max bytes a line can grow to without hitting --delimiter before
+        /// it's logged anyway
+        #[arg(long, value_parser = parse_max_line_length, default_value_t = 64 * 1024)]
+        max_line_length: usize,
+
+        /// For --format hex/hexdump, bytes to accumulate before logging a
+        /// chunk if a read timeout doesn't flush it first
+        #[arg(long, value_parser = parse_chunk_size, default_value_t = 256)]
+        chunk_size: usize,
+    },
+}
+
+/// A named `[profile]` section loaded from a `--config` file, mirroring the
+/// subset of `Command::Read`'s options that are reusable across sessions.
+/// CLI flags passed to `connect` take precedence over these.
+#[derive(Debug, Clone, Default)]
+struct Profile {
+    port: Option<String>,
+    vid: Option<u16>,
+    pid: Option<u16>,
+    r#match: Option<String>,
+    baud: Option<u32>,
+    output: Option<PathBuf>,
+    format: Option<Format>,
+    filter: Option<String>,
+    exclude: Option<String>,
+    line_ending: Option<LineEnding>,
+}
+
+/// Loads the `[profile]` section of `config_path` into a [`Profile`].
+fn load_profile(config_path: &Path, profile: &str) -> Result<Profile, String> {
+    let mut ini = Ini::new();
+    ini.load(config_path)?;
+
+    if !ini.sections().iter().any(|s| s.eq_ignore_ascii_case(profile)) {
+        return Err(format!(
+            "no [{profile}] profile in {}",
+            config_path.display()
+        ));
+    }
+
+    let get = |key: &str| ini.get(profile, key);
+
+    Ok(Profile {
+        port: get("port"),
+        vid: get("vid").map(|s| parse_hex_u16(&s)).transpose()?,
+        pid: get("pid").map(|s| parse_hex_u16(&s)).transpose()?,
+        r#match: get("match"),
+        baud: get("baud")
+            .map(|s| s.parse::<u32>().map_err(|e| e.to_string()))
+            .transpose()?,
+        output: get("output").map(PathBuf::from),
+        format: get("format")
+            .map(|s| <Format as ValueEnum>::from_str(&s, true))
+            .transpose()?,
+        filter: get("filter"),
+        exclude: get("exclude"),
+        line_ending: get("line_ending")
+            .map(|s| <LineEnding as ValueEnum>::from_str(&s, true))
+            .transpose()?,
+    })
+}
+
+/// The config file to use when `--config` isn't given: `slog.ini` in the
+/// current directory, falling back to `$HOME/.config/slog/slog.ini`.
+fn default_config_path() -> Option<PathBuf> {
+    let cwd_config = PathBuf::from("slog.ini");
+    if cwd_config.is_file() {
+        return Some(cwd_config);
+    }
+
+    let home_config = PathBuf::from(std::env::var("HOME").ok()?)
+        .join(".config")
+        .join("slog")
+        .join("slog.ini");
+    home_config.is_file().then_some(home_config)
+}
+
+fn parse_hex_u16(s: &str) -> Result<u16, String> {
+    u16::from_str_radix(s.trim_start_matches("0x"), 16).map_err(|e| e.to_string())
+}
+
+fn parse_chunk_size(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(0) => Err("chunk size must be at least 1".to_string()),
+        Ok(n) => Ok(n),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn parse_max_line_length(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(0) => Err("max line length must be at least 1".to_string()),
+        Ok(n) => Ok(n),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn parse_delimiter(s: &str) -> Result<u8, String> {
+    match s {
+        "\\n" => Ok(b'\n'),
+        "\\r" => Ok(b'\r'),
+        "\\0" => Ok(0),
+        "\\t" => Ok(b'\t'),
+        _ if s.starts_with("0x") => u8::from_str_radix(&s[2..], 16).map_err(|e| e.to_string()),
+        _ => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if c.is_ascii() => Ok(c as u8),
+                _ => Err(format!(
+                    "expected a single ASCII byte, \\n, \\r, \\0, \\t, or 0xNN, got {s:?}"
+                )),
+            }
+        }
+    }
+}
+
+/// Criteria used to pick a single port out of `available_ports()`.
+#[derive(Debug, Clone, Default)]
+struct PortMatcher {
+    vid: Option<u16>,
+    pid: Option<u16>,
+    text: Option<String>,
+}
+
+impl PortMatcher {
+    fn is_empty(&self) -> bool {
+        self.vid.is_none() && self.pid.is_none() && self.text.is_none()
+    }
+
+    fn matches(&self, info: &UsbPortInfo) -> bool {
+        if self.vid.is_some_and(|vid| info.vid != vid) {
+            return false;
+        }
+        if self.pid.is_some_and(|pid| info.pid != pid) {
+            return false;
+        }
+        if let Some(text) = &self.text {
+            let text = text.to_lowercase();
+            let matched = [&info.serial_number, &info.manufacturer, &info.product]
+                .into_iter()
+                .filter_map(|f| f.as_ref())
+                .any(|f| f.to_lowercase().contains(&text));
+            if !matched {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Resolves a port path from an explicit `--port`, or by matching
+/// `PortMatcher` against `available_ports()`. Errors out (listing the
+/// candidates) if zero or more than one port matches.
+fn resolve_port(port: Option<String>, matcher: &PortMatcher) -> Result<String, String> {
+    if let Some(port) = port {
+        return Ok(port);
+    }
+
+    if matcher.is_empty() {
+        return Err("no --port given, and no --vid/--pid/--match filter provided".to_string());
+    }
+
+    let ports = available_ports().map_err(|e| format!("Error listing serial ports: {e}"))?;
+    let matches: Vec<_> = ports
+        .into_iter()
+        .filter(|p| match &p.port_type {
+            SerialPortType::UsbPort(info) => matcher.matches(info),
+            _ => false,
+        })
+        .collect();
+
+    match matches.len() {
+        0 => Err("no port matched the given --vid/--pid/--match filter".to_string()),
+        1 => Ok(matches.into_iter().next().unwrap().port_name),
+        _ => {
+            let names = matches
+                .into_iter()
+                .map(|p| p.port_name)
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(format!("multiple ports matched: {names}"))
+        }
+    }
 }
 
 #[derive(Parser)]
@@ -41,76 +427,541 @@ struct Cli {
 fn main() {
     let cli = Cli::parse();
 
-    let (port_path, output, baud) = match cli.command {
-        Command::Read { port, output, baud } => (port, output, baud),
-        Command::List => {
-            list_ports();
-            return;
+    match cli.command {
+        Command::Read {
+            port,
+            vid,
+            pid,
+            r#match,
+            output,
+            baud,
+            format,
+            chunk_size,
+            reconnect,
+            duplex,
+            line_ending,
+            filter,
+            exclude,
+            highlight,
+            delimiter,
+            max_line_length,
+        } => {
+            let matcher = PortMatcher {
+                vid,
+                pid,
+                text: r#match,
+            };
+            let opts = ReadOptions {
+                output,
+                format,
+                chunk_size,
+                filter: filter.map(|pattern| compile_regex(&pattern)),
+                exclude: exclude.map(|pattern| compile_regex(&pattern)),
+                highlight,
+                delimiter,
+                max_line_length,
+            };
+
+            run_read_command(port, matcher, baud, opts, reconnect, duplex, line_ending);
+        }
+
+        Command::List { vid, pid, r#match } => list_ports(vid, pid, r#match),
+
+        Command::Connect {
+            profile,
+            config,
+            port,
+            vid,
+            pid,
+            r#match,
+            output,
+            baud,
+            format,
+            filter,
+            exclude,
+            line_ending,
+            reconnect,
+            duplex,
+            highlight,
+            delimiter,
+            max_line_length,
+            chunk_size,
+        } => {
+            let config_path = config.or_else(default_config_path).unwrap_or_else(|| {
+                eprintln!(
+                    "no --config given, and no slog.ini found in the current directory or ~/.config/slog/"
+                );
+                ::std::process::exit(1);
+            });
+
+            let profile = load_profile(&config_path, &profile).unwrap_or_else(|e| {
+                eprintln!("{e}");
+                ::std::process::exit(1);
+            });
+
+            let matcher = PortMatcher {
+                vid: vid.or(profile.vid),
+                pid: pid.or(profile.pid),
+                text: r#match.or(profile.r#match),
+            };
+            let opts = ReadOptions {
+                output: output.or(profile.output),
+                format: format.unwrap_or(profile.format.unwrap_or_default()),
+                chunk_size,
+                filter: filter.or(profile.filter).map(|pattern| compile_regex(&pattern)),
+                exclude: exclude.or(profile.exclude).map(|pattern| compile_regex(&pattern)),
+                highlight,
+                delimiter,
+                max_line_length,
+            };
+            let baud = baud.or(profile.baud).unwrap_or_else(|| {
+                eprintln!("no --baud given, and the profile doesn't set one");
+                ::std::process::exit(1);
+            });
+            let line_ending = line_ending.or(profile.line_ending).unwrap_or(LineEnding::Lf);
+
+            run_read_command(
+                port.or(profile.port),
+                matcher,
+                baud,
+                opts,
+                reconnect,
+                duplex,
+                line_ending,
+            );
+        }
+    }
+}
+
+/// Resolves and opens the port (or keeps retrying, for `--reconnect`) and
+/// runs the read loop. Shared by `Command::Read` and `Command::Connect`,
+/// which only differ in how they gather these parameters.
+fn run_read_command(
+    port: Option<String>,
+    matcher: PortMatcher,
+    baud: u32,
+    opts: ReadOptions,
+    reconnect: bool,
+    duplex: bool,
+    line_ending: LineEnding,
+) {
+    if reconnect {
+        run_reconnecting(port, matcher, baud, opts, duplex, line_ending);
+        return;
+    }
+
+    let port_path = match resolve_port(port, &matcher) {
+        Ok(port_path) => port_path,
+        Err(e) => {
+            eprintln!("{e}");
+            ::std::process::exit(1);
         }
     };
 
-    let port = serialport::new(&port_path, baud)
+    match open_port(&port_path, baud) {
+        Ok(port) => {
+            println!("Receiving data on {} at {} baud:", &port_path, baud);
+
+            if duplex {
+                let stdin_rx = spawn_stdin_forwarder();
+                run_duplex(port, opts, line_ending, &stdin_rx);
+            } else {
+                run_reader(port, opts);
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to open \"{}\". Error: {}", &port_path, e);
+            ::std::process::exit(1);
+        }
+    }
+}
+
+fn compile_regex(pattern: &str) -> Regex {
+    Regex::new(pattern).unwrap_or_else(|e| {
+        eprintln!("Invalid regex \"{pattern}\": {e}");
+        ::std::process::exit(1);
+    })
+}
+
+fn open_port(port_path: &str, baud: u32) -> serialport::Result<Box<dyn SerialPort>> {
+    serialport::new(port_path, baud)
         .timeout(Duration::from_millis(10))
-        .open();
+        .open()
+}
 
-    match port {
-        Ok(mut port) => {
-            let mut serial_buf: Vec<u8> = vec![0; 1000];
-            println!("Receiving data on {} at {} baud:", &port_path, baud);
-            let mut accumulated_data = Vec::new();
-
-            loop {
-                match port.read(serial_buf.as_mut_slice()) {
-                    Ok(t) => {
-                        accumulated_data.extend_from_slice(&serial_buf[..t]);
-
-                        // Split the accumulated data by newlines
-                        while let Some(pos) = accumulated_data.iter().position(|&x| x == b'\n') {
-                            let line = accumulated_data.drain(..=pos).collect::<Vec<u8>>();
-                            let timestamp = generate_timestamp().into_bytes();
-
-                            let mut data = Vec::with_capacity(timestamp.len() + line.len());
-                            data.extend_from_slice(&timestamp);
-                            data.extend_from_slice(&line);
-
-                            io::stdout().write_all(&data).unwrap();
-                            io::stdout().flush().unwrap();
-                            if let Some(ref file) = &output {
-                                let mut file = match OpenOptions::new()
-                                    .write(true)
-                                    .append(true)
-                                    .create(true)
-                                    .open(file)
-                                {
-                                    Ok(file) => file,
-                                    Err(e) => {
-                                        eprintln!(
-                                            "Failed to open \"{}\". Error: {}",
-                                            output.as_ref().unwrap().to_str().unwrap(),
-                                            e
-                                        );
-                                        ::std::process::exit(1);
-                                    }
-                                };
-                                file.write_all(&data).unwrap();
-                                file.flush().unwrap();
-                            }
-                        }
+const RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const RECONNECT_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Resolves and opens a port, retrying with exponential backoff (capped at
+/// `RECONNECT_MAX_BACKOFF`) until it succeeds.
+fn connect_with_backoff(
+    port: &Option<String>,
+    matcher: &PortMatcher,
+    baud: u32,
+) -> (String, Box<dyn SerialPort>) {
+    let mut backoff = RECONNECT_INITIAL_BACKOFF;
+    loop {
+        let port_path = match resolve_port(port.clone(), matcher) {
+            Ok(port_path) => port_path,
+            Err(e) => {
+                eprintln!("{e}");
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(RECONNECT_MAX_BACKOFF);
+                continue;
+            }
+        };
+
+        match open_port(&port_path, baud) {
+            Ok(port) => return (port_path, port),
+            Err(e) => {
+                eprintln!("Failed to open \"{}\". Error: {}", &port_path, e);
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(RECONNECT_MAX_BACKOFF);
+            }
+        }
+    }
+}
+
+/// Keeps (re)connecting to the port for as long as the process runs: once
+/// `run_reader`/`run_duplex` gives up after a disconnect, logs a timestamped
+/// marker and reconnects with backoff via [`connect_with_backoff`]. For
+/// `duplex`, the stdin-forwarding thread is spawned once here, before the
+/// loop, and the same receiver is handed to every cycle's `run_duplex` call
+/// (see [`spawn_stdin_forwarder`] for why one per cycle would deadlock).
+fn run_reconnecting(
+    port: Option<String>,
+    matcher: PortMatcher,
+    baud: u32,
+    opts: ReadOptions,
+    duplex: bool,
+    line_ending: LineEnding,
+) {
+    let stdin_rx = duplex.then(spawn_stdin_forwarder);
+
+    loop {
+        let (port_path, serial_port) = connect_with_backoff(&port, &matcher, baud);
+        println!("Receiving data on {} at {} baud:", &port_path, baud);
+
+        if duplex {
+            run_duplex(serial_port, opts.clone(), line_ending, stdin_rx.as_ref().unwrap());
+        } else {
+            run_reader(serial_port, opts.clone());
+        }
+
+        write_logged_line(b"disconnected\n", &opts.output);
+    }
+}
+
+/// Continuously reads from `port` and logs it according to `opts.format`.
+/// Read timeouts are ignored; a read error other than a timeout is logged to
+/// stderr and makes this return.
+fn run_reader(port: Box<dyn SerialPort>, opts: ReadOptions) {
+    let mut reader = io::BufReader::new(port);
+
+    match opts.format {
+        Format::Text => run_text_reader(&mut reader, &opts),
+        Format::Hex | Format::Hexdump => run_chunk_reader(&mut reader, &opts),
+    }
+}
+
+/// Logs complete, `opts.delimiter`-terminated lines read from `reader`.
+/// Partial lines left over after a read timeout are kept across calls to
+/// `read_until` (it appends to `line` in place even when it errors), so a
+/// slow trickle of bytes is still reassembled correctly. `opts.max_line_length`
+/// bounds how long a line can grow without a delimiter, so a device that
+/// never sends one can't grow `line` unbounded.
+fn run_text_reader(mut reader: impl BufRead, opts: &ReadOptions) {
+    let mut line = Vec::new();
+
+    loop {
+        // Cap each read at one byte past `max_line_length`, so a steady stream
+        // with no delimiter still comes back here for the length check below.
+        let limit = (opts.max_line_length + 1).saturating_sub(line.len()) as u64;
+        match (&mut reader).take(limit).read_until(opts.delimiter, &mut line) {
+            Ok(_) if line.last() == Some(&opts.delimiter) => {
+                let complete = std::mem::take(&mut line);
+                if let Some(complete) = apply_text_filters(complete, opts) {
+                    write_logged_line(&complete, &opts.output);
+                }
+            }
+            Ok(_) => (), // delimiter not reached yet; keep accumulating
+            Err(ref e) if e.kind() == io::ErrorKind::TimedOut => (),
+            Err(e) => {
+                eprintln!("{e:?}");
+                return;
+            }
+        }
+
+        if line.len() > opts.max_line_length {
+            eprintln!(
+                "Line exceeded --max-line-length ({} bytes) without a delimiter; flushing it",
+                opts.max_line_length
+            );
+            let overflowed = std::mem::take(&mut line);
+            if let Some(overflowed) = apply_text_filters(overflowed, opts) {
+                write_logged_line(&overflowed, &opts.output);
+            }
+        }
+    }
+}
+
+/// Logs fixed-size chunks read from `reader`, for the binary `--format`s.
+/// A chunk is flushed once it reaches `opts.chunk_size`, or immediately on a
+/// read timeout so a short burst isn't held back waiting to fill a chunk.
+fn run_chunk_reader(mut reader: impl Read, opts: &ReadOptions) {
+    let mut serial_buf: Vec<u8> = vec![0; 1000];
+    let mut accumulated_data = Vec::new();
+    // Tracks the byte position in the overall capture, not just the current
+    // chunk, so `--format hexdump` offsets keep counting up across flushes
+    // instead of restarting at 0 every time a chunk is logged.
+    let mut offset: u64 = 0;
+
+    loop {
+        match reader.read(serial_buf.as_mut_slice()) {
+            Ok(t) => {
+                accumulated_data.extend_from_slice(&serial_buf[..t]);
+                while accumulated_data.len() >= opts.chunk_size {
+                    let chunk = accumulated_data.drain(..opts.chunk_size).collect::<Vec<u8>>();
+                    write_logged_chunk(&chunk, opts.format, offset, &opts.output);
+                    offset += chunk.len() as u64;
+                }
+            }
+            Err(ref e) if e.kind() == io::ErrorKind::TimedOut => {
+                if !accumulated_data.is_empty() {
+                    let chunk = std::mem::take(&mut accumulated_data);
+                    write_logged_chunk(&chunk, opts.format, offset, &opts.output);
+                    offset += chunk.len() as u64;
+                }
+            }
+            Err(e) => {
+                eprintln!("{e:?}");
+                return;
+            }
+        }
+    }
+}
+
+/// Spawns the single, long-lived thread that reads lines typed on stdin, for
+/// duplex mode, and returns the receiving end of its channel.
+///
+/// This must only ever be spawned once per process, not once per
+/// `run_duplex` call: with `--reconnect`, `run_duplex` is re-entered on every
+/// reconnect cycle, and a fresh thread per cycle would leave the previous
+/// cycle's thread parked mid-read holding the process-wide stdin lock
+/// forever (it has no way to know the cycle ended), deadlocking the next
+/// cycle's attempt to lock stdin again. Callers that loop over `run_duplex`
+/// (like [`run_reconnecting`]) spawn this once up front and pass the same
+/// receiver into every cycle.
+fn spawn_stdin_forwarder() -> mpsc::Receiver<String> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let stdin = io::stdin();
+        for line in stdin.lock().lines() {
+            match line {
+                Ok(line) => {
+                    if tx.send(line).is_err() {
+                        break;
                     }
-                    Err(ref e) if e.kind() == io::ErrorKind::TimedOut => (),
-                    Err(e) => eprintln!("{e:?}"),
+                }
+                Err(e) => {
+                    eprintln!("Failed to read from stdin. Error: {e}");
+                    break;
                 }
             }
         }
+    });
+    rx
+}
+
+/// Runs an interactive session: a background thread keeps logging incoming
+/// data (see [`run_reader`]) while the main thread forwards lines received
+/// on `stdin_rx` (from [`spawn_stdin_forwarder`]) to the port, appending
+/// `line_ending`. Polls `stdin_rx` without blocking so that a non-timeout
+/// error on the reader thread (e.g. the device was unplugged) still makes
+/// this return promptly — letting `--reconnect` notice the disconnect
+/// instead of hanging forever waiting on the next line of stdin. Stdin
+/// closing does not make this return; it keeps logging until the reader
+/// thread gives up.
+fn run_duplex(
+    port: Box<dyn SerialPort>,
+    opts: ReadOptions,
+    line_ending: LineEnding,
+    stdin_rx: &mpsc::Receiver<String>,
+) {
+    let reader_port = match port.try_clone() {
+        Ok(p) => p,
         Err(e) => {
-            eprintln!("Failed to open \"{}\". Error: {}", &port_path, e);
+            eprintln!("Failed to clone port for duplex mode. Error: {e}");
             ::std::process::exit(1);
         }
+    };
+
+    let (reader_done_tx, reader_done_rx) = mpsc::channel();
+    thread::spawn(move || {
+        run_reader(reader_port, opts);
+        let _ = reader_done_tx.send(());
+    });
+
+    let mut writer_port = port;
+    loop {
+        match stdin_rx.try_recv() {
+            Ok(line) => {
+                let mut data = line.into_bytes();
+                data.extend_from_slice(line_ending.as_bytes());
+
+                if let Err(e) = writer_port.write_all(&data) {
+                    eprintln!("Failed to write to port. Error: {e}");
+                }
+            }
+            Err(mpsc::TryRecvError::Empty) => {}
+            Err(mpsc::TryRecvError::Disconnected) => {}
+        }
+
+        match reader_done_rx.recv_timeout(Duration::from_millis(20)) {
+            Ok(()) | Err(mpsc::RecvTimeoutError::Disconnected) => return,
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+        }
+    }
+}
+
+/// Applies `opts.filter`/`opts.exclude` to a single complete line (including
+/// its trailing `opts.delimiter` byte, if any). Returns `None` if the line
+/// should be dropped, otherwise the (possibly highlighted) line to log. With
+/// `opts.highlight` set, non-matching lines are kept (not dropped) and
+/// matching lines get their matched span ANSI-colored instead.
+fn apply_text_filters(line: Vec<u8>, opts: &ReadOptions) -> Option<Vec<u8>> {
+    if opts.filter.is_none() && opts.exclude.is_none() {
+        return Some(line);
+    }
+
+    let had_trailing_delimiter = line.last() == Some(&opts.delimiter);
+    let body = if had_trailing_delimiter {
+        &line[..line.len() - 1]
+    } else {
+        &line[..]
+    };
+    let text = String::from_utf8_lossy(body);
+
+    if opts.exclude.as_ref().is_some_and(|re| re.is_match(&text)) {
+        return None;
+    }
+
+    let Some(filter) = &opts.filter else {
+        return Some(line);
+    };
+
+    match filter.find(&text) {
+        Some(m) if opts.highlight => {
+            let mut highlighted = String::with_capacity(text.len() + 9);
+            highlighted.push_str(&text[..m.start()]);
+            highlighted.push_str("\x1b[1;31m");
+            highlighted.push_str(&text[m.start()..m.end()]);
+            highlighted.push_str("\x1b[0m");
+            highlighted.push_str(&text[m.end()..]);
+
+            let mut out = highlighted.into_bytes();
+            if had_trailing_delimiter {
+                out.push(opts.delimiter);
+            }
+            Some(out)
+        }
+        Some(_) => Some(line),
+        None if opts.highlight => Some(line),
+        None => None,
     }
 }
 
-fn list_ports() {
+/// Timestamps `line` and writes it to stdout and, if present, `output`.
+fn write_logged_line(line: &[u8], output: &Option<PathBuf>) {
+    let timestamp = generate_timestamp().into_bytes();
+
+    let mut data = Vec::with_capacity(timestamp.len() + line.len());
+    data.extend_from_slice(&timestamp);
+    data.extend_from_slice(line);
+
+    io::stdout().write_all(&data).unwrap();
+    io::stdout().flush().unwrap();
+    if let Some(file) = output {
+        let mut file = match OpenOptions::new()
+            .write(true)
+            .append(true)
+            .create(true)
+            .open(file)
+        {
+            Ok(file) => file,
+            Err(e) => {
+                eprintln!("Failed to open \"{}\". Error: {}", file.to_str().unwrap(), e);
+                ::std::process::exit(1);
+            }
+        };
+        file.write_all(&data).unwrap();
+        file.flush().unwrap();
+    }
+}
+
+/// Timestamps `chunk` formatted according to `format`, and writes it to
+/// stdout and, if present, `output`. `offset` is this chunk's position in
+/// the overall capture, used by `--format hexdump` to keep its offset column
+/// running across chunks instead of restarting at 0 each flush.
+fn write_logged_chunk(chunk: &[u8], format: Format, offset: u64, output: &Option<PathBuf>) {
+    let formatted = match format {
+        Format::Text => unreachable!("text chunks are logged line by line"),
+        Format::Hex => format_hex(chunk),
+        Format::Hexdump => format_hexdump(chunk, offset),
+    };
+
+    write_logged_line(formatted.as_bytes(), output);
+}
+
+/// Formats `chunk` as space-separated `0x..` bytes, e.g. `0x00 0xff 0x10`.
+fn format_hex(chunk: &[u8]) -> String {
+    let mut out = chunk
+        .iter()
+        .map(|b| format!("{b:#04x}"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    out.push('\n');
+    out
+}
+
+/// Formats `chunk` as a canonical hexdump: a 16-byte-per-row offset, hex,
+/// and ASCII gutter layout, like `hexdump -C`. `base_offset` is this chunk's
+/// position in the overall capture, so offsets keep counting up across
+/// chunks instead of restarting at 0 every flush.
+fn format_hexdump(chunk: &[u8], base_offset: u64) -> String {
+    let mut out = String::new();
+    for (row, bytes) in chunk.chunks(16).enumerate() {
+        let offset = base_offset + (row * 16) as u64;
+        let hex = bytes
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let ascii = bytes
+            .iter()
+            .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+            .collect::<String>();
+        out.push_str(&format!("{offset:08x}  {hex:<47}  |{ascii}|\n"));
+    }
+    out
+}
+
+fn list_ports(vid: Option<u16>, pid: Option<u16>, r#match: Option<String>) {
+    let matcher = PortMatcher {
+        vid,
+        pid,
+        text: r#match,
+    };
+
     if let Ok(ports) = available_ports() {
+        let ports: Vec<_> = ports
+            .into_iter()
+            .filter(|p| match &p.port_type {
+                SerialPortType::UsbPort(info) => matcher.is_empty() || matcher.matches(info),
+                _ => matcher.is_empty(),
+            })
+            .collect();
+
         match ports.len() {
             0 => println!("No ports found."),
             1 => println!("Found 1 port:"),
@@ -176,3 +1027,212 @@ fn generate_timestamp() -> String {
         GREEN = "\x1b[32m",
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usb_info(
+        vid: u16,
+        pid: u16,
+        serial_number: Option<&str>,
+        manufacturer: Option<&str>,
+        product: Option<&str>,
+    ) -> UsbPortInfo {
+        UsbPortInfo {
+            vid,
+            pid,
+            serial_number: serial_number.map(String::from),
+            manufacturer: manufacturer.map(String::from),
+            product: product.map(String::from),
+            #[cfg(feature = "usbportinfo-interface")]
+            interface: None,
+        }
+    }
+
+    #[test]
+    fn parse_delimiter_named_escapes() {
+        assert_eq!(parse_delimiter("\\n").unwrap(), b'\n');
+        assert_eq!(parse_delimiter("\\r").unwrap(), b'\r');
+        assert_eq!(parse_delimiter("\\0").unwrap(), 0);
+        assert_eq!(parse_delimiter("\\t").unwrap(), b'\t');
+    }
+
+    #[test]
+    fn parse_delimiter_hex_byte() {
+        assert_eq!(parse_delimiter("0x41").unwrap(), b'A');
+        assert_eq!(parse_delimiter("0x00").unwrap(), 0);
+        assert!(parse_delimiter("0xzz").is_err());
+    }
+
+    #[test]
+    fn parse_delimiter_single_ascii_char() {
+        assert_eq!(parse_delimiter("a").unwrap(), b'a');
+        assert_eq!(parse_delimiter(";").unwrap(), b';');
+    }
+
+    #[test]
+    fn parse_delimiter_rejects_multi_char_and_empty() {
+        assert!(parse_delimiter("ab").is_err());
+        assert!(parse_delimiter("").is_err());
+    }
+
+    #[test]
+    fn parse_chunk_size_rejects_zero() {
+        assert!(parse_chunk_size("0").is_err());
+        assert_eq!(parse_chunk_size("1").unwrap(), 1);
+        assert_eq!(parse_chunk_size("256").unwrap(), 256);
+    }
+
+    #[test]
+    fn parse_max_line_length_rejects_zero() {
+        assert!(parse_max_line_length("0").is_err());
+        assert_eq!(parse_max_line_length("1").unwrap(), 1);
+        assert_eq!(parse_max_line_length("65536").unwrap(), 65536);
+    }
+
+    #[test]
+    fn apply_text_filters_no_filters_passes_through() {
+        let opts = base_opts();
+        let line = b"hello\n".to_vec();
+        assert_eq!(apply_text_filters(line.clone(), &opts), Some(line));
+    }
+
+    #[test]
+    fn apply_text_filters_drops_non_matching_without_highlight() {
+        let mut opts = base_opts();
+        opts.filter = Some(compile_regex("ERROR"));
+        assert_eq!(apply_text_filters(b"all good\n".to_vec(), &opts), None);
+        assert_eq!(
+            apply_text_filters(b"ERROR: boom\n".to_vec(), &opts),
+            Some(b"ERROR: boom\n".to_vec())
+        );
+    }
+
+    #[test]
+    fn apply_text_filters_keeps_non_matching_with_highlight() {
+        let mut opts = base_opts();
+        opts.filter = Some(compile_regex("ERROR"));
+        opts.highlight = true;
+        assert_eq!(
+            apply_text_filters(b"all good\n".to_vec(), &opts),
+            Some(b"all good\n".to_vec())
+        );
+        assert_eq!(
+            apply_text_filters(b"an ERROR here\n".to_vec(), &opts),
+            Some(b"an \x1b[1;31mERROR\x1b[0m here\n".to_vec())
+        );
+    }
+
+    #[test]
+    fn apply_text_filters_exclude_wins_over_highlight() {
+        let mut opts = base_opts();
+        opts.filter = Some(compile_regex("ERROR"));
+        opts.exclude = Some(compile_regex("ignore"));
+        opts.highlight = true;
+        assert_eq!(
+            apply_text_filters(b"ERROR: please ignore\n".to_vec(), &opts),
+            None
+        );
+    }
+
+    #[test]
+    fn apply_text_filters_uses_configured_delimiter() {
+        let mut opts = base_opts();
+        opts.delimiter = b'\r';
+        opts.filter = Some(compile_regex("ERROR$"));
+        assert_eq!(
+            apply_text_filters(b"ERROR\r".to_vec(), &opts),
+            Some(b"ERROR\r".to_vec())
+        );
+    }
+
+    fn base_opts() -> ReadOptions {
+        ReadOptions {
+            output: None,
+            format: Format::Text,
+            chunk_size: 256,
+            filter: None,
+            exclude: None,
+            highlight: false,
+            delimiter: b'\n',
+            max_line_length: 1024,
+        }
+    }
+
+    #[test]
+    fn format_hex_formats_bytes_space_separated() {
+        assert_eq!(format_hex(&[0x00, 0xff, 0x10]), "0x00 0xff 0x10\n");
+        assert_eq!(format_hex(&[]), "\n");
+    }
+
+    #[test]
+    fn format_hexdump_lays_out_offset_hex_and_ascii() {
+        let out = format_hexdump(b"hello", 0);
+        assert_eq!(
+            out,
+            "00000000  68 65 6c 6c 6f                                   |hello|\n"
+        );
+    }
+
+    #[test]
+    fn format_hexdump_wraps_at_16_bytes_per_row() {
+        let chunk: Vec<u8> = (0..20).collect();
+        let out = format_hexdump(&chunk, 0);
+        assert_eq!(out.lines().count(), 2);
+        assert!(out.lines().next().unwrap().starts_with("00000000"));
+        assert!(out.lines().nth(1).unwrap().starts_with("00000010"));
+    }
+
+    #[test]
+    fn format_hexdump_continues_offset_from_base() {
+        let out = format_hexdump(b"hello", 0x20);
+        assert!(out.starts_with("00000020"));
+    }
+
+    #[test]
+    fn port_matcher_empty_matches_anything() {
+        let matcher = PortMatcher::default();
+        assert!(matcher.is_empty());
+        assert!(matcher.matches(&usb_info(0x1234, 0x5678, None, None, None)));
+    }
+
+    #[test]
+    fn port_matcher_filters_by_vid_and_pid() {
+        let matcher = PortMatcher {
+            vid: Some(0x04d8),
+            pid: Some(0x000a),
+            text: None,
+        };
+        assert!(matcher.matches(&usb_info(0x04d8, 0x000a, None, None, None)));
+        assert!(!matcher.matches(&usb_info(0x04d8, 0x000b, None, None, None)));
+        assert!(!matcher.matches(&usb_info(0x0000, 0x000a, None, None, None)));
+    }
+
+    #[test]
+    fn port_matcher_filters_by_text_case_insensitively() {
+        let matcher = PortMatcher {
+            vid: None,
+            pid: None,
+            text: Some("Arduino".to_string()),
+        };
+        assert!(matcher.matches(&usb_info(0, 0, None, Some("Arduino LLC"), None)));
+        assert!(matcher.matches(&usb_info(0, 0, Some("sn-arduino-1"), None, None)));
+        assert!(!matcher.matches(&usb_info(0, 0, None, None, Some("Other device"))));
+    }
+
+    #[test]
+    fn resolve_port_prefers_explicit_port() {
+        let matcher = PortMatcher::default();
+        assert_eq!(
+            resolve_port(Some("/dev/ttyUSB0".to_string()), &matcher),
+            Ok("/dev/ttyUSB0".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_port_errors_without_port_or_filter() {
+        let matcher = PortMatcher::default();
+        assert!(resolve_port(None, &matcher).is_err());
+    }
+}